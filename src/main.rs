@@ -1,116 +1,623 @@
 //! Matrix rain in the terminal.
 
+use clap::{Parser, ValueEnum};
 use crossterm::cursor::{Hide, MoveTo, Show};
 use crossterm::event::{read, Event, KeyCode};
-use crossterm::execute;
 use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use crossterm::terminal::{Clear, ClearType};
 use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
 use rand::Rng;
 use std::cmp;
+use std::collections::HashMap;
 use std::io;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::io::{BufWriter, Stdout, Write};
+use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 
-/// How long to sleep between animation frames.
-const FRAME_SLEEP: Duration = Duration::from_millis(50);
+/// Command line options controlling the rain's pace, look, and feel.
+#[derive(Parser)]
+#[command(about = "Matrix-style digital rain for your terminal", long_about = None)]
+struct Cli {
+    /// Frames to render per second.
+    #[arg(long, default_value_t = 20.0)]
+    fps: f64,
 
-/// Minimum length of a droplet.
-const DROPLET_MIN_LENGTH: u16 = 2;
+    /// Minimum length of a droplet, in cells.
+    #[arg(long, default_value_t = 2)]
+    min_length: u16,
 
-/// Maximum length of a droplet.
-const DROPLET_MAX_LENGTH: u16 = 20;
+    /// Maximum length of a droplet, in cells.
+    #[arg(long, default_value_t = 20)]
+    max_length: u16,
 
-/// Minimum speed of a droplet.
-const DROPLET_MIN_SPEED: f32 = 0.2;
+    /// Minimum speed of a droplet, in cells per frame.
+    #[arg(long, default_value_t = 0.2)]
+    min_speed: f32,
 
-/// Maximum speed of a droplet.
-const DROPLET_MAX_SPEED: f32 = 1.0;
+    /// Maximum speed of a droplet, in cells per frame.
+    #[arg(long, default_value_t = 1.0)]
+    max_speed: f32,
 
-/// Base color of the droplet.
-const BASE_COLOR: (u8, u8, u8) = (170, 255, 170);
+    /// Base color of the droplets: a named color (green, red, blue, white, yellow, cyan,
+    /// magenta) or an "R,G,B" triple.
+    #[arg(long, default_value = "green")]
+    color: String,
+
+    /// Direction the rain falls.
+    #[arg(long, value_enum, default_value_t = Direction::Down)]
+    direction: Direction,
+
+    /// Glyph set droplets are drawn from.
+    #[arg(long, value_enum, default_value_t = CliCharSet::Katakana)]
+    char_set: CliCharSet,
+
+    /// Custom glyphs to sample from, overriding --char-set.
+    #[arg(long)]
+    custom_chars: Option<String>,
+
+    /// Message to reveal in the center of the screen while the rain falls around it.
+    #[arg(long)]
+    message: Option<String>,
+}
+
+/// Named glyph pools selectable from the command line; see [`CharSet`] for the glyphs each one
+/// resolves to.
+#[derive(Clone, Copy, ValueEnum)]
+enum CliCharSet {
+    Katakana,
+    Ascii,
+    Binary,
+    Hex,
+    Greek,
+}
+
+impl From<CliCharSet> for CharSet {
+    fn from(char_set: CliCharSet) -> Self {
+        match char_set {
+            CliCharSet::Katakana => CharSet::katakana(),
+            CliCharSet::Ascii => CharSet::ascii(),
+            CliCharSet::Binary => CharSet::binary(),
+            CliCharSet::Hex => CharSet::hex(),
+            CliCharSet::Greek => CharSet::greek(),
+        }
+    }
+}
+
+/// Parses `--color`, accepting either a named color or an "R,G,B" triple.
+fn parse_color(value: &str) -> Result<(u8, u8, u8), String> {
+    let components: Vec<&str> = value.split(',').collect();
+    if let [r, g, b] = components[..] {
+        let parse_component = |component: &str| {
+            component
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid color component: \"{component}\""))
+        };
+        return Ok((
+            parse_component(r)?,
+            parse_component(g)?,
+            parse_component(b)?,
+        ));
+    }
+    match value.to_lowercase().as_str() {
+        "green" => Ok((170, 255, 170)),
+        "red" => Ok((255, 100, 100)),
+        "blue" => Ok((100, 170, 255)),
+        "white" => Ok((255, 255, 255)),
+        "yellow" => Ok((255, 255, 150)),
+        "cyan" => Ok((150, 255, 255)),
+        "magenta" => Ok((255, 150, 255)),
+        _ => Err(format!(
+            "unknown color \"{value}\"; use a named color or \"R,G,B\""
+        )),
+    }
+}
+
+/// Runtime-tunable animation parameters, parsed from the command line instead of baked in as
+/// `const`s, so the rain can be retuned and rethemed without recompiling.
+struct Config {
+    frame_sleep: Duration,
+    droplet_min_length: u16,
+    droplet_max_length: u16,
+    droplet_min_speed: f32,
+    droplet_max_speed: f32,
+    base_color: (u8, u8, u8),
+}
+
+impl Config {
+    fn from_cli(cli: &Cli) -> Result<Self, String> {
+        if cli.fps.is_nan() || cli.fps <= 0.0 {
+            return Err(format!("--fps must be greater than 0, got {}", cli.fps));
+        }
+        if cli.min_length == 0 {
+            return Err("--min-length must be greater than 0".to_string());
+        }
+        if cli.max_length == 0 {
+            return Err("--max-length must be greater than 0".to_string());
+        }
+        if cli.min_length > cli.max_length {
+            return Err(format!(
+                "--min-length ({}) must not be greater than --max-length ({})",
+                cli.min_length, cli.max_length
+            ));
+        }
+        if cli.min_speed.is_nan() || cli.max_speed.is_nan() || cli.min_speed > cli.max_speed {
+            return Err(format!(
+                "--min-speed ({}) must not be greater than --max-speed ({})",
+                cli.min_speed, cli.max_speed
+            ));
+        }
+        if cli.custom_chars.as_deref().is_some_and(str::is_empty) {
+            return Err("--custom-chars must not be empty".to_string());
+        }
+        Ok(Self {
+            frame_sleep: Duration::from_secs_f64(1.0 / cli.fps),
+            droplet_min_length: cli.min_length,
+            droplet_max_length: cli.max_length,
+            droplet_min_speed: cli.min_speed,
+            droplet_max_speed: cli.max_speed,
+            base_color: parse_color(&cli.color)?,
+        })
+    }
+}
+
+/// How many frames a newly spawned droplet's head takes to swell up to full brightness.
+const SWELL_FRAMES: u16 = 6;
+
+/// How much a droplet's velocity changes each frame it advances, speeding up while falling and
+/// slowing down while bouncing.
+const GRAVITY: f32 = 0.05;
+
+/// Fraction of a droplet's velocity it keeps, in reverse, when it bounces off the far edge.
+const BOUNCE_DAMPING: f32 = 0.4;
+
+/// How much a bouncing droplet's intensity is scaled down each frame it advances, as it fades out.
+const BOUNCE_FADE: f32 = 0.85;
+
+/// Floor on a bouncing droplet's velocity, so gravity slowing it down never stalls it in place.
+const BOUNCE_MIN_VELOCITY: f32 = 0.05;
+
+/// Direction in which droplets travel across the screen.
+#[derive(Clone, Copy, ValueEnum)]
+enum Direction {
+    Down,
+    Up,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Whether droplets for this direction travel along columns (`Down`/`Up`, one droplet per
+    /// column) or along rows (`Left`/`Right`, one droplet per row).
+    fn is_vertical(self) -> bool {
+        matches!(self, Direction::Down | Direction::Up)
+    }
+
+    /// Whether `Droplet::position` counts up towards the screen coordinate (`Down`/`Right`) or
+    /// down from the far edge (`Up`/`Left`).
+    fn is_forward(self) -> bool {
+        matches!(self, Direction::Down | Direction::Right)
+    }
+}
+
+/// The stage of a droplet's life cycle.
+#[derive(Clone, Copy, PartialEq)]
+enum Phase {
+    /// Head is stationary at the spawn position, brightening up to full intensity.
+    Swelling,
+    /// Head accelerates along the direction of travel under gravity.
+    Falling,
+    /// Head has reached the far edge and rebounds, dimmer, shorter, and fading out.
+    Bouncing,
+}
 
 /// Holds information about a single droplet.
 struct Droplet {
-    row: u16,
+    position: u16, // Distance travelled along the direction of motion.
     len: u16,
     max_len: u16,
-    frame: f32, // 1.0 -> draw next frame
-    speed: f32, // (0.0, 1.0]
+    frame: f32,    // 1.0 -> draw next frame
+    velocity: f32, // Speed along the direction of travel; grows/shrinks under gravity.
+    phase: Phase,
+    intensity: f32, // 0.0 (invisible) to 1.0 (full brightness).
+}
+
+/// Creates a freshly spawned droplet at the given starting position, swelling up from nothing.
+fn new_droplet(position: u16, config: &Config) -> Droplet {
+    let mut rng = rand::thread_rng();
+    Droplet {
+        position,
+        len: 1,
+        max_len: rng.gen_range(config.droplet_min_length..=config.droplet_max_length),
+        frame: 0.0,
+        velocity: rng.gen_range(config.droplet_min_speed..=config.droplet_max_speed),
+        phase: Phase::Swelling,
+        intensity: 0.0,
+    }
+}
+
+/// Resizes `droplets` to hold exactly `tracks` entries, dropping any that no longer fit and
+/// spawning fresh droplets spread across the full extent for any new tracks, so they blend in
+/// with rain that's already falling.
+fn resize_droplets(droplets: &mut Vec<Droplet>, tracks: u16, extent: u16, config: &Config) {
+    let mut rng = rand::thread_rng();
+    droplets.truncate(tracks as usize);
+    while droplets.len() < tracks as usize {
+        droplets.push(new_droplet(rng.gen_range(0..cmp::max(extent, 1)), config));
+    }
+}
+
+/// A pool of glyphs that droplets sample their characters from.
+///
+/// Built once at startup and then shared for the lifetime of the animation, so the expensive
+/// part (deciding which code points belong to the set) only happens a single time.
+struct CharSet {
+    glyphs: Vec<char>,
+}
+
+impl CharSet {
+    /// Half-width katakana, the glyphs used by the original Matrix digital rain.
+    fn katakana() -> Self {
+        // 'ｦ'..'ﾝ'
+        Self::from_range(0xFF66..0xFF9D)
+    }
+
+    /// Printable ASCII, from '!' to '~'.
+    fn ascii() -> Self {
+        Self::from_range(0x21..0x7F)
+    }
+
+    /// Just '0' and '1', for a classic binary look.
+    fn binary() -> Self {
+        Self::custom("01")
+    }
+
+    /// Hex digits '0'-'9' and 'A'-'F'.
+    fn hex() -> Self {
+        Self::custom("0123456789ABCDEF")
+    }
+
+    /// The Greek alphabet, upper- and lowercase.
+    fn greek() -> Self {
+        Self::custom("ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩαβγδεζηθικλμνξοπρστυφχψω")
+    }
+
+    /// A user-supplied string; duplicate characters are kept, so repeated glyphs are sampled
+    /// more often.
+    fn custom(glyphs: &str) -> Self {
+        Self {
+            glyphs: glyphs.chars().collect(),
+        }
+    }
+
+    /// Builds a char set from a range of Unicode code points, skipping any that aren't valid
+    /// `char`s (e.g. surrogates).
+    fn from_range(range: std::ops::Range<u32>) -> Self {
+        Self {
+            glyphs: range.filter_map(char::from_u32).collect(),
+        }
+    }
 }
 
-/// Generate a random character.
-fn random_char() -> char {
+/// Generate a random character from the given char set.
+fn random_char(char_set: &CharSet) -> char {
     let mut rng = rand::thread_rng();
-    let katakana_start = 0xFF66; // Half-width katakana 'ｦ'
-    let katakana_end = 0xFF9D; // Half-width katakana 'ﾝ'
-    let random_char = rng.gen_range(katakana_start..katakana_end);
-    char::from_u32(random_char).unwrap()
+    let index = rng.gen_range(0..char_set.glyphs.len());
+    char_set.glyphs[index]
+}
+
+/// Color a revealed message is drawn in, distinct from any droplet's gradient.
+const MESSAGE_COLOR: Color = Color::Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+
+/// A banner of text rendered over the rain, centered on the screen. The mask also serves as the
+/// per-cell glyph lookup, since the rain needs to know both which cells are reserved for the
+/// message and what to draw in them.
+struct Message {
+    text: String,
+    mask: HashMap<(u16, u16), char>,
+}
+
+impl Message {
+    /// Lays `text` out centered on a `cols` by `rows` screen. `text` may contain `\n` for a
+    /// multi-line banner; spaces are treated as transparent and don't reserve a cell. Lines and
+    /// columns that don't fit on screen are truncated, so a message larger than the terminal
+    /// still draws (just cropped) instead of producing off-screen mask coordinates.
+    fn new(text: &str, cols: u16, rows: u16) -> Self {
+        let lines: Vec<&str> = text.lines().take(rows as usize).collect();
+        let height = lines.len() as u16;
+        let width = lines
+            .iter()
+            .map(|line| cmp::min(line.chars().count() as u16, cols))
+            .max()
+            .unwrap_or(0);
+        let top = rows.saturating_sub(height) / 2;
+        let left = cols.saturating_sub(width) / 2;
+
+        let mut mask = HashMap::new();
+        for (line_index, line) in lines.iter().enumerate() {
+            for (col_index, glyph) in line.chars().take(cols as usize).enumerate() {
+                if glyph != ' ' {
+                    mask.insert((left + col_index as u16, top + line_index as u16), glyph);
+                }
+            }
+        }
+        Self {
+            text: text.to_string(),
+            mask,
+        }
+    }
+}
+
+/// Whether `(col, row)` belongs to the revealed message, if any.
+fn is_masked(message: Option<&Message>, col: u16, row: u16) -> bool {
+    message.is_some_and(|message| message.mask.contains_key(&(col, row)))
 }
 
 /// Linear gradient of the droplet's color based on distance from the bottom (0) to the top
-/// (len - 1).
-fn color_gradient(droplet: &Droplet, distance: u16) -> Color {
-    let scale = (droplet.len as f64 - distance as f64) / droplet.len as f64;
+/// (len - 1), scaled by the droplet's overall intensity so swelling and fading read correctly.
+/// Here "bottom" means the droplet's head, whichever way the droplet is travelling.
+fn color_gradient(droplet: &Droplet, distance: u16, config: &Config) -> Color {
+    let scale =
+        (droplet.len as f64 - distance as f64) / droplet.len as f64 * droplet.intensity as f64;
     Color::Rgb {
-        r: (BASE_COLOR.0 as f64 * scale) as u8,
-        g: (BASE_COLOR.1 as f64 * scale) as u8,
-        b: (BASE_COLOR.2 as f64 * scale) as u8,
+        r: (config.base_color.0 as f64 * scale) as u8,
+        g: (config.base_color.1 as f64 * scale) as u8,
+        b: (config.base_color.2 as f64 * scale) as u8,
+    }
+}
+
+/// A single screen cell as it was last drawn, so `draw_next_frame` can skip re-drawing cells
+/// whose content hasn't changed since the previous frame.
+#[derive(Clone, Copy, PartialEq)]
+struct Cell {
+    glyph: char,
+    color: Color,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            glyph: ' ',
+            color: Color::Reset,
+        }
+    }
+}
+
+/// Mirrors what is currently drawn on screen, one entry per terminal cell.
+struct Backbuffer {
+    cols: u16,
+    cells: Vec<Cell>,
+}
+
+impl Backbuffer {
+    fn new(cols: u16, rows: u16) -> Self {
+        Self {
+            cols,
+            cells: vec![Cell::default(); cols as usize * rows as usize],
+        }
+    }
+
+    fn index(&self, col: u16, row: u16) -> usize {
+        row as usize * self.cols as usize + col as usize
+    }
+}
+
+/// Queues a glyph write for `(col, row)` unless the backbuffer already shows that exact glyph
+/// and color, and skips re-emitting `SetForegroundColor` when it matches the last color queued
+/// this frame, collapsing runs of same-colored cells into a single color change.
+fn queue_cell(
+    writer: &mut BufWriter<Stdout>,
+    backbuffer: &mut Backbuffer,
+    last_color: &mut Option<Color>,
+    col: u16,
+    row: u16,
+    glyph: char,
+    color: Color,
+) -> io::Result<()> {
+    let index = backbuffer.index(col, row);
+    let cell = Cell { glyph, color };
+    if backbuffer.cells[index] == cell {
+        return Ok(());
     }
+    queue!(writer, MoveTo(col, row))?;
+    if *last_color != Some(color) {
+        queue!(writer, SetForegroundColor(color))?;
+        *last_color = Some(color);
+    }
+    queue!(writer, Print(glyph))?;
+    backbuffer.cells[index] = cell;
+    Ok(())
+}
+
+/// Translates a droplet's `position` (distance travelled along the direction of motion) plus an
+/// offset from its head into a coordinate along that same axis, or `None` if it falls off the
+/// screen.
+fn motion_coord(forward: bool, extent: u16, position: u16, offset: u16) -> Option<u16> {
+    let coord = if forward {
+        position as i32 - offset as i32
+    } else {
+        extent as i32 - 1 - position as i32 + offset as i32
+    };
+    (0..extent as i32).contains(&coord).then_some(coord as u16)
+}
+
+/// Combines a track index (the column or row a droplet is fixed to) with a coordinate along the
+/// direction of motion into actual screen coordinates.
+fn screen_coord(direction: Direction, track: u16, coord: u16) -> (u16, u16) {
+    if direction.is_vertical() {
+        (track, coord) // track is a column, coord is a row.
+    } else {
+        (coord, track) // track is a row, coord is a column.
+    }
+}
+
+/// The per-frame inputs that stay fixed for the whole frame (and usually for many frames in a
+/// row): screen size, glyph pool, rain direction, any revealed message, and the tunable
+/// parameters. Bundled together so `draw_next_frame` takes one reference instead of a positional
+/// argument per field.
+struct FrameContext<'a> {
+    cols: u16,
+    rows: u16,
+    char_set: &'a CharSet,
+    direction: Direction,
+    message: Option<&'a Message>,
+    config: &'a Config,
 }
 
 /// Draw and advance to the next frame.
-fn draw_next_frame(cols: u16, rows: u16, droplets: &mut Vec<Droplet>) -> io::Result<()> {
+fn draw_next_frame(
+    ctx: &FrameContext,
+    droplets: &mut Vec<Droplet>,
+    backbuffer: &mut Backbuffer,
+    writer: &mut BufWriter<Stdout>,
+) -> io::Result<()> {
     let mut rng = rand::thread_rng();
-    for col in 0..cols {
-        let droplet = &mut droplets[col as usize];
-        droplet.frame += droplet.speed;
+    let mut last_color = None;
+    let (tracks, extent) = if ctx.direction.is_vertical() {
+        (ctx.cols, ctx.rows)
+    } else {
+        (ctx.rows, ctx.cols)
+    };
+
+    if let Some(message) = ctx.message {
+        for (&(col, row), &glyph) in &message.mask {
+            queue_cell(
+                writer,
+                backbuffer,
+                &mut last_color,
+                col,
+                row,
+                glyph,
+                MESSAGE_COLOR,
+            )?;
+        }
+    }
+
+    for track in 0..tracks {
+        let droplet = &mut droplets[track as usize];
+
+        if droplet.phase == Phase::Swelling {
+            droplet.frame += 1.0 / SWELL_FRAMES as f32;
+            droplet.intensity = droplet.frame.min(1.0);
+            if let Some(coord) =
+                motion_coord(ctx.direction.is_forward(), extent, droplet.position, 0)
+            {
+                let (col, row) = screen_coord(ctx.direction, track, coord);
+                if !is_masked(ctx.message, col, row) {
+                    queue_cell(
+                        writer,
+                        backbuffer,
+                        &mut last_color,
+                        col,
+                        row,
+                        random_char(ctx.char_set),
+                        color_gradient(droplet, 0, ctx.config),
+                    )?;
+                }
+            }
+            if droplet.frame >= 1.0 {
+                droplet.frame = 0.0;
+                droplet.phase = Phase::Falling;
+            }
+            continue;
+        }
+
+        droplet.frame += droplet.velocity.abs();
         if droplet.frame < 1.0 {
             continue;
         }
-        if droplet.row >= rows + droplet.len {
-            // Droplet out of screen, create a new one.
-            *droplet = Droplet {
-                row: rng.gen_range(0..rows / 4), // New droplets at the top of the screen.
-                len: 1,
-                max_len: rng.gen_range(DROPLET_MIN_LENGTH..=DROPLET_MAX_LENGTH),
-                frame: 1.0,
-                speed: rng.gen_range(DROPLET_MIN_SPEED..=DROPLET_MAX_SPEED),
-            };
+        droplet.frame -= 1.0;
+
+        if droplet.position >= extent + droplet.len || droplet.intensity <= 0.0 {
+            // Droplet out of screen or fully faded out, create a new one near the spawn edge.
+            *droplet = new_droplet(rng.gen_range(0..cmp::max(extent / 4, 1)), ctx.config);
             continue;
         }
+
+        let forward = ctx.direction.is_forward() != (droplet.phase == Phase::Bouncing);
         for distance in 0..droplet.len + 1 {
-            if droplet.row >= distance && droplet.row - distance < rows {
-                execute!(
-                    io::stdout(),
-                    MoveTo(col, droplet.row - distance),
-                    SetForegroundColor(color_gradient(droplet, distance)),
-                    Print(random_char()),
-                )?;
+            if let Some(coord) = motion_coord(forward, extent, droplet.position, distance) {
+                let (col, row) = screen_coord(ctx.direction, track, coord);
+                if !is_masked(ctx.message, col, row) {
+                    queue_cell(
+                        writer,
+                        backbuffer,
+                        &mut last_color,
+                        col,
+                        row,
+                        random_char(ctx.char_set),
+                        color_gradient(droplet, distance, ctx.config),
+                    )?;
+                }
             }
         }
-        if droplet.row > droplet.len - 1 {
-            // Fade totally when length reached.
-            execute!(
-                io::stdout(),
-                MoveTo(col, droplet.row - droplet.len),
-                SetForegroundColor(Color::Reset),
-                Print(' '),
-            )?;
+        if droplet.position > droplet.len - 1 {
+            // Fade totally when length reached, unless the message is showing through here.
+            if let Some(coord) = motion_coord(forward, extent, droplet.position, droplet.len) {
+                let (col, row) = screen_coord(ctx.direction, track, coord);
+                if !is_masked(ctx.message, col, row) {
+                    queue_cell(
+                        writer,
+                        backbuffer,
+                        &mut last_color,
+                        col,
+                        row,
+                        ' ',
+                        Color::Reset,
+                    )?;
+                }
+            }
         }
+
         // Move to next frame and extend the droplet if needed.
-        droplet.frame -= 1.0;
-        droplet.row += 1;
+        droplet.position += 1;
         droplet.len = cmp::min(droplet.len + 1, droplet.max_len);
+        match droplet.phase {
+            Phase::Falling => {
+                droplet.velocity += GRAVITY;
+                if droplet.position >= extent.saturating_sub(1) {
+                    // Reached the far edge: rebound as a dimmer, shorter droplet.
+                    droplet.phase = Phase::Bouncing;
+                    droplet.position = 0;
+                    droplet.len = cmp::max(droplet.len / 3, 1);
+                    droplet.velocity *= BOUNCE_DAMPING;
+                    droplet.intensity *= 0.6;
+                }
+            }
+            Phase::Bouncing => {
+                droplet.velocity = (droplet.velocity - GRAVITY).max(BOUNCE_MIN_VELOCITY);
+                droplet.intensity *= BOUNCE_FADE;
+            }
+            Phase::Swelling => unreachable!("handled above"),
+        }
     }
-    Ok(())
+    writer.flush()
+}
+
+/// Events forwarded from the input-reading thread to the draw loop.
+enum InputEvent {
+    Quit,
+    Resize(u16, u16),
 }
 
 /// Setup the terminal, initialize the droplets, and spawn key check and drawing loops.
 fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    let config =
+        Config::from_cli(&cli).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let char_set = match &cli.custom_chars {
+        Some(glyphs) => CharSet::custom(glyphs),
+        None => cli.char_set.into(),
+    };
+    let direction = cli.direction;
+
     let mut rng = rand::thread_rng();
 
     enable_raw_mode()?;
@@ -122,37 +629,83 @@ fn main() -> io::Result<()> {
         Clear(ClearType::All)
     )?;
 
-    let (cols, rows) = crossterm::terminal::size()?;
+    let (mut cols, mut rows) = crossterm::terminal::size()?;
 
-    let mut droplets: Vec<Droplet> = (0..cols)
+    let (tracks, extent) = if direction.is_vertical() {
+        (cols, rows)
+    } else {
+        (rows, cols)
+    };
+    // Droplets start out already falling at a random position, so the rain looks established
+    // from the very first frame instead of every track swelling up in lockstep.
+    let mut droplets: Vec<Droplet> = (0..tracks)
         .map(|_| {
-            let len = rng.gen_range(DROPLET_MIN_LENGTH..=DROPLET_MAX_LENGTH);
+            let len = rng.gen_range(config.droplet_min_length..=config.droplet_max_length);
             Droplet {
-                row: rng.gen_range(0..rows),
-                len: len,
+                position: rng.gen_range(0..extent),
+                len,
                 max_len: len,
                 frame: 1.0,
-                speed: rng.gen_range(DROPLET_MIN_SPEED..=DROPLET_MAX_SPEED),
+                velocity: rng.gen_range(config.droplet_min_speed..=config.droplet_max_speed),
+                phase: Phase::Falling,
+                intensity: 1.0,
             }
         })
         .collect();
 
-    let running = Arc::new(AtomicBool::new(true));
-    let running_clone = running.clone();
+    // No message is revealed unless one was passed on the command line.
+    let mut message = cli
+        .message
+        .as_deref()
+        .map(|text| Message::new(text, cols, rows));
 
-    thread::spawn(move || {
-        while running_clone.load(Ordering::Relaxed) {
-            if let Ok(Event::Key(key)) = read() {
-                if key.code == KeyCode::Char('q') {
-                    running_clone.store(false, Ordering::Relaxed);
-                }
+    let mut backbuffer = Backbuffer::new(cols, rows);
+    let mut writer = BufWriter::new(io::stdout());
+
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || loop {
+        let event = match read() {
+            Ok(Event::Key(key)) if key.code == KeyCode::Char('q') => Some(InputEvent::Quit),
+            Ok(Event::Resize(new_cols, new_rows)) => Some(InputEvent::Resize(new_cols, new_rows)),
+            _ => None,
+        };
+        if let Some(event) = event {
+            if tx.send(event).is_err() {
+                break;
             }
         }
     });
 
-    while running.load(Ordering::Relaxed) {
-        draw_next_frame(cols, rows, &mut droplets)?;
-        thread::sleep(FRAME_SLEEP);
+    'draw: loop {
+        for event in rx.try_iter() {
+            match event {
+                InputEvent::Quit => break 'draw,
+                InputEvent::Resize(new_cols, new_rows) => {
+                    cols = new_cols;
+                    rows = new_rows;
+                    let (tracks, extent) = if direction.is_vertical() {
+                        (cols, rows)
+                    } else {
+                        (rows, cols)
+                    };
+                    resize_droplets(&mut droplets, tracks, extent, &config);
+                    backbuffer = Backbuffer::new(cols, rows);
+                    message = message.map(|message| Message::new(&message.text, cols, rows));
+                    execute!(writer, Clear(ClearType::All))?;
+                }
+            }
+        }
+        let ctx = FrameContext {
+            cols,
+            rows,
+            char_set: &char_set,
+            direction,
+            message: message.as_ref(),
+            config: &config,
+        };
+        draw_next_frame(&ctx, &mut droplets, &mut backbuffer, &mut writer)?;
+        thread::sleep(config.frame_sleep);
     }
 
     execute!(io::stdout(), LeaveAlternateScreen, ResetColor, Show)?;